@@ -0,0 +1,698 @@
+//! I2C master driver
+
+use core::future::poll_fn;
+use core::marker::PhantomData;
+use core::sync::atomic::Ordering;
+use core::task::Poll;
+
+use embassy_time::{block_for, Duration, Instant};
+
+pub use super::AddressMode;
+use super::{
+    validate_address, Async, Blocking, Error, Info, Instance, MasterDma, Mode, Result, SclPin, SdaPin,
+    TransferError, I2C_REMEDIATION, I2C_WAKERS, REMEDIATON_MASTER_STOP,
+};
+use crate::dma;
+
+/// Below this length, the per-byte FIFO/interrupt path has lower latency than setting
+/// up and tearing down a DMA transfer; at or above it DMA wins.
+const DMA_THRESHOLD: usize = 8;
+
+/// Arms a "send STOP on drop" guard around an in-flight master transfer. If the
+/// enclosing future is dropped before the transfer completes (a `select` losing the
+/// race, an outer timeout, ...), the next `mstpending` interrupt issues a STOP instead
+/// of leaving the bus wedged mid-transaction. [`Self::disarm`] on the success path.
+struct StopOnCancel<'a> {
+    info: &'a Info,
+    armed: bool,
+}
+
+impl<'a> StopOnCancel<'a> {
+    fn new(info: &'a Info) -> Self {
+        Self { info, armed: true }
+    }
+
+    fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for StopOnCancel<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            I2C_REMEDIATION[self.info.index].fetch_or(REMEDIATON_MASTER_STOP, Ordering::AcqRel);
+        }
+    }
+}
+
+/// Clears `MSTDMAEN` on drop, including when the enclosing future is cancelled
+/// mid-transfer and the code that normally clears it after `with_timeout`'s await never
+/// runs. Unlike [`StopOnCancel`] this doesn't need to be deferred to the next interrupt:
+/// clearing the bit is a plain register write, so it's done directly here, and clearing
+/// it a second time on the normal completion path is harmless.
+struct ClearDmaEnOnDrop {
+    info: Info,
+}
+
+impl Drop for ClearDmaEnOnDrop {
+    fn drop(&mut self) {
+        self.info.regs.mstctl().write(|w| w.mstdmaen().clear_bit());
+    }
+}
+
+/// Standard mode bus frequency (100 kHz).
+pub const STANDARD_MODE_FREQUENCY: u32 = 100_000;
+/// Fast mode bus frequency (400 kHz).
+pub const FAST_MODE_FREQUENCY: u32 = 400_000;
+/// Fast-mode Plus bus frequency (1 MHz).
+pub const FAST_MODE_PLUS_FREQUENCY: u32 = 1_000_000;
+
+// Flexcomm function clock feeding the I2C block, used to derive the `CLKDIV`/`MSTTIME`
+// dividers below. This mirrors the fixed function clock assumed elsewhere in the HAL.
+const FLEXCOMM_FUNCTION_CLOCK_HZ: u32 = 24_000_000;
+
+// `MSTTIME`'s MSTSCLHIGH/MSTSCLLOW fields are 4 bits wide and encode `cycles - 2`, so
+// each SCL half period is realizable in [2, 17] post-`CLKDIV` flexcomm clock cycles.
+const MIN_SCL_CYCLES: u32 = 2;
+const MAX_SCL_CYCLES: u32 = 17;
+
+/// I2C master bus configuration.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Config {
+    /// Target bus frequency, in Hz. [`STANDARD_MODE_FREQUENCY`], [`FAST_MODE_FREQUENCY`]
+    /// and [`FAST_MODE_PLUS_FREQUENCY`] are supported out of the box; other values are
+    /// accepted as long as the flexcomm function clock can realize them through the
+    /// `CLKDIV`/`MSTTIME` dividers, otherwise [`Error::UnsupportedConfiguration`] is
+    /// returned from the constructor.
+    pub frequency: u32,
+
+    /// Timeout for each wait within a transaction: in blocking mode for each buffer
+    /// written or read, in async mode for each byte on the FIFO path, and for the
+    /// address phase and the DMA transfer separately on the DMA path. A wait that
+    /// exceeds it aborts the transaction with [`TransferError::Timeout`]. The total
+    /// duration of a transaction is not bounded by this value.
+    pub timeout: Duration,
+
+    /// Automatically run [`I2c::recover_bus`] after a transaction fails with
+    /// [`TransferError::ArbitrationLoss`] or [`TransferError::StartStopError`], so the
+    /// next transaction doesn't inherit a wedged bus.
+    pub auto_recover: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            frequency: STANDARD_MODE_FREQUENCY,
+            timeout: Duration::from_millis(10),
+            auto_recover: false,
+        }
+    }
+}
+
+// Compute the `CLKDIV` divider and `MSTTIME` high/low cycle counts that realize
+// `frequency` Hz out of the flexcomm function clock, assuming a 50/50 SCL duty cycle.
+fn compute_timing(frequency: u32) -> Result<(u16, u8, u8)> {
+    if frequency == 0 {
+        return Err(Error::UnsupportedConfiguration);
+    }
+
+    for divval in 1..=65536u32 {
+        let scaled_clock = FLEXCOMM_FUNCTION_CLOCK_HZ / divval;
+        let total_cycles = scaled_clock / frequency;
+
+        // A coarser divider only ever lowers total_cycles further, so once we're below
+        // the minimum achievable split there is no point trying larger divval values.
+        if total_cycles < 2 * MIN_SCL_CYCLES {
+            break;
+        }
+        if total_cycles > 2 * MAX_SCL_CYCLES {
+            continue;
+        }
+
+        let high_cycles = total_cycles / 2;
+        let low_cycles = total_cycles - high_cycles;
+        if (MIN_SCL_CYCLES..=MAX_SCL_CYCLES).contains(&high_cycles)
+            && (MIN_SCL_CYCLES..=MAX_SCL_CYCLES).contains(&low_cycles)
+        {
+            let clkdiv = u16::try_from(divval - 1).map_err(|_| Error::UnsupportedConfiguration)?;
+            let msttime_high = u8::try_from(high_cycles - MIN_SCL_CYCLES).map_err(|_| Error::UnsupportedConfiguration)?;
+            let msttime_low = u8::try_from(low_cycles - MIN_SCL_CYCLES).map_err(|_| Error::UnsupportedConfiguration)?;
+            return Ok((clkdiv, msttime_high, msttime_low));
+        }
+    }
+
+    Err(Error::UnsupportedConfiguration)
+}
+
+/// I2C master driver.
+///
+/// `C` is the DMA channel used for transfers at or above [`DMA_THRESHOLD`] bytes; use
+/// [`dma::NoDma`] (the default, and the only option in [`Blocking`] mode) to always use
+/// the per-byte FIFO/interrupt path instead.
+pub struct I2c<'d, T: Instance, M: Mode, S: SclPin<T>, D: SdaPin<T>, C: MasterDma<T> = dma::NoDma> {
+    info: Info,
+    timeout: Duration,
+    auto_recover: bool,
+    // Half the configured SCL period; `recover_bus` holds each bit-banged edge for this
+    // long so a clock-stretching/stuck target has time to observe it.
+    scl_half_period: Duration,
+    scl: S,
+    sda: D,
+    dma: C,
+    _instance: PhantomData<T>,
+    _mode: PhantomData<M>,
+    _lifetime: PhantomData<&'d ()>,
+}
+
+impl<'d, T: Instance, S: SclPin<T>, D: SdaPin<T>> I2c<'d, T, Blocking, S, D, dma::NoDma> {
+    /// Create a new blocking I2C master driver.
+    pub fn new_blocking(_peripheral: T, scl: S, sda: D, config: Config) -> Result<Self> {
+        scl.as_scl();
+        sda.as_sda();
+
+        Self::new_inner(config, scl, sda, dma::NoDma)
+    }
+}
+
+impl<'d, T: Instance, S: SclPin<T>, D: SdaPin<T>, C: MasterDma<T>> I2c<'d, T, Async, S, D, C> {
+    /// Create a new async I2C master driver.
+    ///
+    /// Pass [`dma::NoDma`] for `dma` to always use the FIFO/interrupt path, or a real
+    /// DMA channel implementing [`MasterDma<T>`] to offload transfers of
+    /// [`DMA_THRESHOLD`] bytes or more.
+    pub fn new_async(
+        _peripheral: T,
+        scl: S,
+        sda: D,
+        dma: C,
+        _irq: impl crate::interrupt::typelevel::Binding<T::Interrupt, super::InterruptHandler<T>>,
+        config: Config,
+    ) -> Result<Self> {
+        scl.as_scl();
+        sda.as_sda();
+
+        Self::new_inner(config, scl, sda, dma)
+    }
+}
+
+impl<'d, T: Instance, M: Mode, S: SclPin<T>, D: SdaPin<T>, C: MasterDma<T>> I2c<'d, T, M, S, D, C> {
+    fn new_inner(config: Config, scl: S, sda: D, dma: C) -> Result<Self> {
+        let info = T::info();
+        let (clkdiv, msttime_high, msttime_low) = compute_timing(config.frequency)?;
+
+        let i2c = info.regs;
+        // SAFETY: we own the only handle to this flexcomm instance's registers at this
+        // point in construction, and these fields are only ever touched here and from
+        // the interrupt handler, which does not write CLKDIV/MSTTIME.
+        unsafe {
+            i2c.clkdiv().write(|w| w.divval().bits(clkdiv));
+            i2c.msttime()
+                .write(|w| w.mstsclhigh().bits(msttime_high).mstsclow().bits(msttime_low));
+        }
+        i2c.cfg().write(|w| w.msten().set_bit());
+
+        // `frequency` was already proven realizable by `compute_timing` above, so this
+        // can't divide by zero. Work in nanoseconds and round up: at
+        // `FAST_MODE_PLUS_FREQUENCY` (1 MHz) and above, `Duration::from_micros(500_000 /
+        // frequency)` truncates to zero, which would turn every `recover_bus` edge delay
+        // into a no-op right at the speed recovery is most likely to be needed.
+        let half_period_ns = (500_000_000u64 + config.frequency as u64 - 1) / config.frequency as u64;
+        let scl_half_period = Duration::from_nanos(half_period_ns);
+
+        Ok(Self {
+            info,
+            timeout: config.timeout,
+            auto_recover: config.auto_recover,
+            scl_half_period,
+            scl,
+            sda,
+            dma,
+            _instance: PhantomData,
+            _mode: PhantomData,
+            _lifetime: PhantomData,
+        })
+    }
+
+    /// Check for an arbitration loss or START/STOP error latched since the last call,
+    /// clearing whichever fired.
+    fn check_bus_errors(&self) -> Result<()> {
+        let intstat = self.info.regs.intstat().read();
+        if intstat.mstarbloss().bit_is_set() {
+            self.info.regs.intenclr().write(|w| w.mstarblossclr().set_bit());
+            return Err(Error::Transfer(TransferError::ArbitrationLoss));
+        }
+        if intstat.mstststperr().bit_is_set() {
+            self.info.regs.intenclr().write(|w| w.mstststperrclr().set_bit());
+            return Err(Error::Transfer(TransferError::StartStopError));
+        }
+        Ok(())
+    }
+
+    /// Check `MSTSTATE` for a target NACKing the address or a data byte. Only
+    /// meaningful right after `mstpending` has been observed set.
+    fn check_ack(&self) -> Result<()> {
+        match self.info.regs.stat().read().mststate().bits() {
+            3 => Err(Error::Transfer(TransferError::AddressNack)),
+            4 => Err(Error::Transfer(TransferError::WriteFail)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Run [`Self::recover_bus`] if `result` is an error [`Config::auto_recover`] is
+    /// configured to recover from, then pass `result` through unchanged.
+    fn maybe_auto_recover(&mut self, result: Result<()>) -> Result<()> {
+        if self.auto_recover
+            && matches!(
+                result,
+                Err(Error::Transfer(TransferError::ArbitrationLoss | TransferError::StartStopError))
+            )
+        {
+            self.recover_bus();
+        }
+        result
+    }
+
+    /// Issue a START (or repeated START) with the given target address, validating it
+    /// first so that a bad address never reaches the bus.
+    fn start(&mut self, address: u16, mode: AddressMode, read: bool) -> Result<()> {
+        validate_address(address, mode)?;
+
+        let i2c = self.info.regs;
+        let first_byte = match mode {
+            AddressMode::SevenBit => ((address as u8) << 1) | (read as u8),
+            AddressMode::TenBit => super::TEN_BIT_PREFIX | (((address >> 8) as u8) << 1) | (read as u8),
+        };
+
+        // SAFETY: single-writer access to this instance's data/control registers, as
+        // guaranteed by `&mut self`.
+        unsafe {
+            i2c.mstdat().write(|w| w.data().bits(first_byte));
+        }
+        i2c.mstctl().write(|w| w.mststart().set_bit());
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        self.info.regs.mstctl().write(|w| w.mststop().set_bit());
+    }
+
+    fn blocking_wait_pending(&self, deadline: Instant) -> Result<()> {
+        loop {
+            self.check_bus_errors()?;
+            if self.info.regs.intstat().read().mstpending().bit_is_set() {
+                return self.check_ack();
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::Transfer(TransferError::Timeout));
+            }
+        }
+    }
+
+    async fn wait_pending(&self) -> Result<()> {
+        let index = self.info.index;
+        let i2c = self.info.regs;
+        let guard = StopOnCancel::new(&self.info);
+        let fut = poll_fn(|cx| {
+            I2C_WAKERS[index].register(cx.waker());
+            if let Err(e) = self.check_bus_errors() {
+                return Poll::Ready(Err(e));
+            }
+            if i2c.intstat().read().mstpending().bit_is_set() {
+                Poll::Ready(self.check_ack())
+            } else {
+                i2c.intenset().write(|w| w.mstpendingen().set_bit());
+                Poll::Pending
+            }
+        });
+
+        match embassy_time::with_timeout(self.timeout, fut).await {
+            Ok(result) => {
+                if result.is_ok() {
+                    guard.disarm();
+                }
+                result
+            }
+            Err(_) => Err(Error::Transfer(TransferError::Timeout)),
+        }
+    }
+
+    /// Push `write` out over the FIFO one byte at a time, blocking on each `mstpending`.
+    fn blocking_write_bytes(&mut self, write: &[u8]) -> Result<()> {
+        let deadline = Instant::now() + self.timeout;
+        for &byte in write {
+            self.blocking_wait_pending(deadline)?;
+            // SAFETY: see `start`.
+            unsafe {
+                self.info.regs.mstdat().write(|w| w.data().bits(byte));
+            }
+            self.info.regs.mstctl().write(|w| w.mstcontinue().set_bit());
+        }
+        self.blocking_wait_pending(deadline)
+    }
+
+    /// Pull `read.len()` bytes in over the FIFO one byte at a time, blocking on each
+    /// `mstpending`.
+    fn blocking_read_bytes(&mut self, read: &mut [u8]) -> Result<()> {
+        let deadline = Instant::now() + self.timeout;
+        for byte in read.iter_mut() {
+            self.blocking_wait_pending(deadline)?;
+            *byte = self.info.regs.mstdat().read().data().bits();
+            self.info.regs.mstctl().write(|w| w.mstcontinue().set_bit());
+        }
+        Ok(())
+    }
+
+    /// Blocking write transaction addressed with `address` in [`AddressMode::SevenBit`].
+    pub fn blocking_write(&mut self, address: u8, write: &[u8]) -> Result<()> {
+        let result = self.start(address as u16, AddressMode::SevenBit, false).and_then(|()| {
+            // Issue STOP whether or not the write itself succeeded: a NACK leaves
+            // MSTSTATE in 3/4 and only a STOP (or repeated START) returns the state
+            // machine to idle, so a bailing `?` here would wedge the bus on every
+            // address probe instead of just reporting the error.
+            let result = self.blocking_write_bytes(write);
+            self.stop();
+            result
+        });
+        self.maybe_auto_recover(result)
+    }
+
+    /// Blocking read transaction addressed with `address` in [`AddressMode::SevenBit`].
+    pub fn blocking_read(&mut self, address: u8, read: &mut [u8]) -> Result<()> {
+        let result = self.start(address as u16, AddressMode::SevenBit, true).and_then(|()| {
+            // See `blocking_write`: STOP must run on the error path too.
+            let result = self.blocking_read_bytes(read);
+            self.stop();
+            result
+        });
+        self.maybe_auto_recover(result)
+    }
+
+    /// Run an `embedded-hal` [`Operation`](embedded_hal::i2c::Operation) sequence
+    /// addressed with `address` in [`AddressMode::SevenBit`].
+    ///
+    /// Consecutive operations of the same direction are coalesced onto a single
+    /// START, so only a direction change between a write and a read emits a repeated
+    /// START; the whole sequence is framed by one STOP at the end.
+    pub fn blocking_transaction(&mut self, address: u8, operations: &mut [embedded_hal::i2c::Operation<'_>]) -> Result<()> {
+        let result = self.blocking_transaction_inner(address, operations);
+        self.maybe_auto_recover(result)
+    }
+
+    fn blocking_transaction_inner(&mut self, address: u8, operations: &mut [embedded_hal::i2c::Operation<'_>]) -> Result<()> {
+        use embedded_hal::i2c::Operation;
+
+        // Tracks whether a START has actually gone out yet, so a validation failure on
+        // the very first operation (nothing on the bus to clean up) is distinguished
+        // from a NACK/timeout partway through (STOP must still run to unwedge MSTSTATE).
+        let mut started = false;
+        let result = (|| -> Result<()> {
+            let mut ops = operations.iter_mut().peekable();
+            while let Some(op) = ops.next() {
+                match op {
+                    Operation::Write(write) => {
+                        self.start(address as u16, AddressMode::SevenBit, false)?;
+                        started = true;
+                        self.blocking_write_bytes(write)?;
+                        while matches!(ops.peek(), Some(Operation::Write(_))) {
+                            let Some(Operation::Write(write)) = ops.next() else {
+                                unreachable!()
+                            };
+                            self.blocking_write_bytes(write)?;
+                        }
+                    }
+                    Operation::Read(read) => {
+                        self.start(address as u16, AddressMode::SevenBit, true)?;
+                        started = true;
+                        self.blocking_read_bytes(read)?;
+                        while matches!(ops.peek(), Some(Operation::Read(_))) {
+                            let Some(Operation::Read(read)) = ops.next() else {
+                                unreachable!()
+                            };
+                            self.blocking_read_bytes(read)?;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        })();
+
+        if started {
+            self.stop();
+        }
+        result
+    }
+
+    /// Manually recover a bus left stuck by a target holding SDA low, or by an
+    /// arbitration loss / START-STOP error that left the flexcomm block out of sync
+    /// with the bus. Callers typically invoke this after seeing
+    /// [`TransferError::ArbitrationLoss`] or [`TransferError::StartStopError`].
+    ///
+    /// Temporarily switches SCL and SDA back to plain GPIO (via
+    /// [`SclPin::as_scl_gpio`]/[`SdaPin::as_sda_gpio`]). If SDA is observed low, it
+    /// clocks SCL up to nine times (stopping early once SDA releases) and then issues a
+    /// manual STOP, whether or not SDA has released. It then restores the flexcomm I2C
+    /// pin functions and resets the peripheral. Each edge is held for half
+    /// the configured [`Config::frequency`] period so a clock-stretching/stuck target
+    /// actually has time to observe it.
+    pub fn recover_bus(&mut self) {
+        self.scl.as_scl_gpio();
+        self.sda.as_sda_gpio();
+
+        // Release SCL and see whether the target is actually holding SDA low; if it
+        // isn't, there's nothing to recover.
+        self.scl.set_gpio_level(true);
+        block_for(self.scl_half_period);
+        if !self.sda.is_gpio_high() {
+            for _ in 0..9 {
+                self.scl.set_gpio_level(false);
+                block_for(self.scl_half_period);
+                self.scl.set_gpio_level(true);
+                block_for(self.scl_half_period);
+                if self.sda.is_gpio_high() {
+                    break;
+                }
+            }
+
+            // Manual STOP: SDA low-to-high while SCL stays high.
+            self.sda.set_gpio_level(false);
+            block_for(self.scl_half_period);
+            self.sda.set_gpio_level(true);
+            block_for(self.scl_half_period);
+        }
+
+        self.scl.as_scl();
+        self.sda.as_sda();
+        self.reset_peripheral();
+    }
+
+    /// Reset the flexcomm I2C peripheral back to the state [`Self::new_inner`] leaves
+    /// it in, after [`Self::recover_bus`] has restored the pin functions.
+    fn reset_peripheral(&mut self) {
+        let i2c = self.info.regs;
+        i2c.cfg().write(|w| w.msten().clear_bit());
+        i2c.cfg().write(|w| w.msten().set_bit());
+    }
+}
+
+impl<'d, T: Instance, S: SclPin<T>, D: SdaPin<T>, C: MasterDma<T>> I2c<'d, T, Async, S, D, C> {
+    /// Async write transaction addressed with `address` in [`AddressMode::SevenBit`].
+    ///
+    /// Writes of [`DMA_THRESHOLD`] bytes or more are carried out by `C` when it is a
+    /// real DMA channel; shorter writes, and all writes on [`dma::NoDma`], use the
+    /// `mstpending`-driven FIFO path.
+    pub async fn write(&mut self, address: u8, write: &[u8]) -> Result<()> {
+        let result = self.write_inner(address, write).await;
+        self.maybe_auto_recover(result)
+    }
+
+    async fn write_inner(&mut self, address: u8, write: &[u8]) -> Result<()> {
+        self.start(address as u16, AddressMode::SevenBit, false)?;
+        // Capture the result and always STOP once a START has gone out, the same as
+        // `blocking_write`: a NACK/timeout/arbitration loss must still return MSTSTATE
+        // to idle instead of bailing out via `?` before `self.stop()` runs.
+        let result = if !C::IS_NODMA && write.len() >= DMA_THRESHOLD {
+            self.dma_write(write).await
+        } else {
+            self.write_bytes(write).await
+        };
+        self.stop();
+        result
+    }
+
+    /// Async read transaction addressed with `address` in [`AddressMode::SevenBit`].
+    ///
+    /// Reads of [`DMA_THRESHOLD`] bytes or more are carried out by `C` when it is a
+    /// real DMA channel; shorter reads, and all reads on [`dma::NoDma`], use the
+    /// `mstpending`-driven FIFO path.
+    pub async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<()> {
+        let result = self.read_inner(address, read).await;
+        self.maybe_auto_recover(result)
+    }
+
+    async fn read_inner(&mut self, address: u8, read: &mut [u8]) -> Result<()> {
+        self.start(address as u16, AddressMode::SevenBit, true)?;
+        // See `write_inner`: STOP must run on the error path too.
+        let result = if !C::IS_NODMA && read.len() >= DMA_THRESHOLD {
+            self.dma_read(read).await
+        } else {
+            self.read_bytes(read).await
+        };
+        self.stop();
+        result
+    }
+
+    /// Run an `embedded-hal` [`Operation`](embedded_hal::i2c::Operation) sequence
+    /// addressed with `address` in [`AddressMode::SevenBit`].
+    ///
+    /// Consecutive operations of the same direction are coalesced onto a single
+    /// START, so only a direction change between a write and a read emits a repeated
+    /// START; the whole sequence is framed by one STOP at the end. Each byte still goes
+    /// through the `mstpending`-driven FIFO path, not `C`'s DMA channel.
+    pub async fn transaction(&mut self, address: u8, operations: &mut [embedded_hal::i2c::Operation<'_>]) -> Result<()> {
+        let result = self.transaction_inner(address, operations).await;
+        self.maybe_auto_recover(result)
+    }
+
+    async fn transaction_inner(&mut self, address: u8, operations: &mut [embedded_hal::i2c::Operation<'_>]) -> Result<()> {
+        use embedded_hal::i2c::Operation;
+
+        // See `blocking_transaction_inner`: `started` distinguishes a validation
+        // failure on the very first operation (nothing on the bus yet) from a
+        // NACK/timeout partway through (STOP must still run to unwedge MSTSTATE).
+        let mut started = false;
+        let mut ops = operations.iter_mut().peekable();
+        let result: Result<()> = async {
+            while let Some(op) = ops.next() {
+                match op {
+                    Operation::Write(write) => {
+                        self.start(address as u16, AddressMode::SevenBit, false)?;
+                        started = true;
+                        self.write_bytes(write).await?;
+                        while matches!(ops.peek(), Some(Operation::Write(_))) {
+                            let Some(Operation::Write(write)) = ops.next() else {
+                                unreachable!()
+                            };
+                            self.write_bytes(write).await?;
+                        }
+                    }
+                    Operation::Read(read) => {
+                        self.start(address as u16, AddressMode::SevenBit, true)?;
+                        started = true;
+                        self.read_bytes(read).await?;
+                        while matches!(ops.peek(), Some(Operation::Read(_))) {
+                            let Some(Operation::Read(read)) = ops.next() else {
+                                unreachable!()
+                            };
+                            self.read_bytes(read).await?;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        if started {
+            self.stop();
+        }
+        result
+    }
+
+    /// Push `write` out over the FIFO one byte at a time, awaiting each `mstpending`.
+    async fn write_bytes(&mut self, write: &[u8]) -> Result<()> {
+        for &byte in write {
+            self.wait_pending().await?;
+            // SAFETY: see `start`.
+            unsafe {
+                self.info.regs.mstdat().write(|w| w.data().bits(byte));
+            }
+            self.info.regs.mstctl().write(|w| w.mstcontinue().set_bit());
+        }
+        self.wait_pending().await
+    }
+
+    /// Pull `read.len()` bytes in over the FIFO one byte at a time, awaiting each
+    /// `mstpending`.
+    async fn read_bytes(&mut self, read: &mut [u8]) -> Result<()> {
+        for byte in read.iter_mut() {
+            self.wait_pending().await?;
+            *byte = self.info.regs.mstdat().read().data().bits();
+            self.info.regs.mstctl().write(|w| w.mstcontinue().set_bit());
+        }
+        Ok(())
+    }
+
+    /// Hand `write` to the DMA engine, programming the flexcomm master TX request line
+    /// and awaiting completion instead of servicing `mstpending` per byte.
+    async fn dma_write(&mut self, write: &[u8]) -> Result<()> {
+        let i2c = self.info.regs;
+        let guard = StopOnCancel::new(&self.info);
+
+        // The address phase still lands on the first `mstpending`, exactly like the
+        // first iteration of `write_bytes`; check it before hand-off so a target that
+        // doesn't ack the address surfaces `AddressNack` instead of the DMA engine
+        // stalling silently until `Config::timeout` and reporting a misleading Timeout.
+        self.wait_pending().await?;
+
+        i2c.mstctl().write(|w| w.mstdmaen().set_bit());
+        let _clear_dma_en = ClearDmaEnOnDrop { info: self.info };
+        // SAFETY: `write` is valid for the lifetime of the returned transfer, and
+        // `self.dma` is uniquely owned by this driver for the duration of the await.
+        let transfer = unsafe { dma::write(&mut self.dma, write, i2c.mstdat().as_ptr().cast()) };
+
+        let result = match embassy_time::with_timeout(self.timeout, transfer).await {
+            Ok(()) => Ok(()),
+            Err(_) => Err(Error::Transfer(TransferError::Timeout)),
+        };
+
+        if result.is_ok() {
+            guard.disarm();
+        }
+        result
+    }
+
+    /// Hand `read` to the DMA engine, programming the flexcomm master RX request line
+    /// and awaiting completion instead of servicing `mstpending` per byte.
+    async fn dma_read(&mut self, read: &mut [u8]) -> Result<()> {
+        let i2c = self.info.regs;
+        let guard = StopOnCancel::new(&self.info);
+
+        // See `dma_write`: surface the address-phase ack/arbitration state before
+        // handing the buffer to DMA.
+        self.wait_pending().await?;
+
+        i2c.mstctl().write(|w| w.mstdmaen().set_bit());
+        let _clear_dma_en = ClearDmaEnOnDrop { info: self.info };
+        // SAFETY: see `dma_write`.
+        let transfer = unsafe { dma::read(&mut self.dma, i2c.mstdat().as_ptr().cast(), read) };
+
+        let result = match embassy_time::with_timeout(self.timeout, transfer).await {
+            Ok(()) => Ok(()),
+            Err(_) => Err(Error::Transfer(TransferError::Timeout)),
+        };
+
+        if result.is_ok() {
+            guard.disarm();
+        }
+        result
+    }
+}
+
+impl<'d, T: Instance, M: Mode, S: SclPin<T>, D: SdaPin<T>, C: MasterDma<T>> embedded_hal::i2c::ErrorType
+    for I2c<'d, T, M, S, D, C>
+{
+    type Error = Error;
+}
+
+impl<'d, T: Instance, S: SclPin<T>, D: SdaPin<T>, C: MasterDma<T>> embedded_hal::i2c::I2c for I2c<'d, T, Blocking, S, D, C> {
+    fn transaction(&mut self, address: u8, operations: &mut [embedded_hal::i2c::Operation<'_>]) -> Result<()> {
+        Self::blocking_transaction(self, address, operations)
+    }
+}
+
+impl<'d, T: Instance, S: SclPin<T>, D: SdaPin<T>, C: MasterDma<T>> embedded_hal_async::i2c::I2c for I2c<'d, T, Async, S, D, C> {
+    async fn transaction(&mut self, address: u8, operations: &mut [embedded_hal::i2c::Operation<'_>]) -> Result<()> {
+        Self::transaction(self, address, operations).await
+    }
+}
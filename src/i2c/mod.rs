@@ -48,6 +48,14 @@ pub enum Error {
     /// configuration requested is not supported
     UnsupportedConfiguration,
 
+    /// the requested target address falls in a range reserved by the I2C
+    /// specification (7-bit `0x00..=0x07` and `0x78..=0x7F`)
+    AddressReserved(u16),
+
+    /// the requested target address does not fit the addressing mode in use
+    /// (7-bit addresses must be `< 0x80`, 10-bit addresses `<= 0x3FF`)
+    AddressOutOfRange(u16),
+
     /// transaction failure types
     Transfer(TransferError),
 }
@@ -58,6 +66,18 @@ impl From<TransferError> for Error {
     }
 }
 
+impl embedded_hal::i2c::Error for Error {
+    fn kind(&self) -> embedded_hal::i2c::ErrorKind {
+        use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+        match self {
+            Error::Transfer(TransferError::AddressNack) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+            Error::Transfer(TransferError::WriteFail) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+            Error::Transfer(TransferError::ArbitrationLoss) => ErrorKind::ArbitrationLoss,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
 mod sealed {
     /// simply seal a trait
     pub trait Sealed {}
@@ -135,6 +155,17 @@ const REMEDIATON_NONE: u8 = 0b0000_0000;
 const REMEDIATON_MASTER_STOP: u8 = 0b0000_0001;
 const REMEDIATON_SLAVE_NAK: u8 = 0b0000_0010;
 
+// Slot (`SLVIDX`) that matched the most recent `slvpending`, captured by the interrupt
+// handler and by the slave driver when it reads a command, so that both the slave driver and a `REMEDIATON_SLAVE_NAK` cleanup racing
+// against it agree on which of the four address slots the NAK/command belongs to.
+static I2C_SLAVE_MATCHED_SLOT: [AtomicU8; I2C_COUNT] = [const { AtomicU8::new(0) }; I2C_COUNT];
+
+// Slot latched by `slave::NakOnCancel` at the moment an in-flight slave transaction
+// future is dropped, so the `REMEDIATON_SLAVE_NAK` branch below only NAKs if that same
+// slot is still the one driving the interrupt, rather than an unrelated transaction that
+// started on a different slot in the meantime.
+static I2C_SLAVE_NAK_SLOT: [AtomicU8; I2C_COUNT] = [const { AtomicU8::new(0) }; I2C_COUNT];
+
 /// Force the remediation state to NONE. To be used when first initializing
 /// a peripheral. This is meant to cover the extremely esoteric state where:
 ///
@@ -164,6 +195,47 @@ async fn wait_remediation_complete(info: &Info) {
 /// Ten bit addresses start with first byte 0b11110XXX
 pub const TEN_BIT_PREFIX: u8 = 0b11110 << 3;
 
+/// 7-bit addresses `0x00..=0x07` and `0x78..=0x7F` are reserved by the I2C
+/// specification (general call, HS-mode master codes, device ID, and so on).
+const RESERVED_7BIT_LOW: core::ops::RangeInclusive<u16> = 0x00..=0x07;
+const RESERVED_7BIT_HIGH: core::ops::RangeInclusive<u16> = 0x78..=0x7F;
+
+/// Target addressing mode, gating which address range [`validate_address`] accepts.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum AddressMode {
+    /// 7-bit addressing, the common case.
+    SevenBit,
+    /// 10-bit addressing, framed on the wire with the [`TEN_BIT_PREFIX`].
+    TenBit,
+}
+
+/// Validate a target address before a START condition is issued.
+///
+/// In [`AddressMode::SevenBit`] this rejects the reserved ranges above as well as any
+/// value `>= 0x80`. In [`AddressMode::TenBit`] the full `0..=0x3FF` space is valid, since
+/// the reserved 7-bit ranges don't apply once the address is framed behind the
+/// [`TEN_BIT_PREFIX`].
+pub(crate) fn validate_address(address: u16, mode: AddressMode) -> Result<()> {
+    match mode {
+        AddressMode::SevenBit => {
+            if address >= 0x80 {
+                return Err(Error::AddressOutOfRange(address));
+            }
+            if RESERVED_7BIT_LOW.contains(&address) || RESERVED_7BIT_HIGH.contains(&address) {
+                return Err(Error::AddressReserved(address));
+            }
+            Ok(())
+        }
+        AddressMode::TenBit => {
+            if address > 0x3FF {
+                return Err(Error::AddressOutOfRange(address));
+            }
+            Ok(())
+        }
+    }
+}
+
 /// I2C interrupt handler.
 pub struct InterruptHandler<T: Instance> {
     _phantom: PhantomData<T>,
@@ -195,10 +267,21 @@ impl<T: Instance> interrupt::typelevel::Handler<T::Interrupt> for InterruptHandl
         }
 
         if i2c.intstat().read().slvpending().bit_is_set() {
+            // Capture which of the four address slots is live *before* doing anything
+            // else, so a matched-address read by the slave driver after being woken
+            // reports the transaction that actually triggered this interrupt rather
+            // than a stale one.
+            let matched_slot = i2c.stat().read().slvidx().bits();
+            I2C_SLAVE_MATCHED_SLOT[T::index()].store(matched_slot, Ordering::Release);
+
             // Retrieve and mask off the remediation flags
             let rem = I2C_REMEDIATION[T::index()].fetch_and(!REMEDIATON_SLAVE_NAK, Ordering::AcqRel);
 
-            if (rem & REMEDIATON_SLAVE_NAK) != 0 {
+            // Only NAK if this interrupt is still for the slot that was in-flight when
+            // the cancelled future was dropped; if a different slot has since started a
+            // transaction, this NAK is stale and would otherwise hit the wrong one.
+            if (rem & REMEDIATON_SLAVE_NAK) != 0 && I2C_SLAVE_NAK_SLOT[T::index()].load(Ordering::Acquire) == matched_slot
+            {
                 i2c.slvctl().write(|w| w.slvnack().set_bit());
             }
             i2c.intenclr().write(|w| w.slvpendingclr().set_bit());
@@ -216,12 +299,53 @@ impl<T: Instance> interrupt::typelevel::Handler<T::Interrupt> for InterruptHandl
 pub trait SclPin<Instance>: Pin + sealed::Sealed + PeripheralType {
     /// convert the pin to appropriate function for SCL usage
     fn as_scl(&self);
+
+    /// Temporarily switch the pin to a plain open-drain GPIO output, for manual
+    /// clocking during [`master::I2c::recover_bus`]. Call [`SclPin::as_scl`] again
+    /// afterwards to restore I2C function.
+    fn as_scl_gpio(&self) {
+        self.set_function(crate::iopctl::Function::F0)
+            .set_pull(crate::iopctl::Pull::None)
+            .enable_input_buffer()
+            .disable_analog_multiplex()
+            .set_drive_mode(crate::gpio::DriveMode::OpenDrain)
+            .set_input_inverter(crate::gpio::Inverter::Disabled);
+    }
+
+    /// Drive the pin high (released) or low while in [`SclPin::as_scl_gpio`] mode.
+    fn set_gpio_level(&self, high: bool) {
+        crate::gpio::set_level(self, high);
+    }
 }
 
 /// io configuration trait for easier configuration
 pub trait SdaPin<Instance>: Pin + sealed::Sealed + PeripheralType {
     /// convert the pin to appropriate function for SDA usage
     fn as_sda(&self);
+
+    /// Temporarily switch the pin to a plain open-drain GPIO, to sample SDA and drive
+    /// the manual STOP during [`master::I2c::recover_bus`]. Call [`SdaPin::as_sda`] again afterwards to
+    /// restore I2C function.
+    fn as_sda_gpio(&self) {
+        self.set_function(crate::iopctl::Function::F0)
+            .set_pull(crate::iopctl::Pull::None)
+            .enable_input_buffer()
+            .disable_analog_multiplex()
+            .set_drive_mode(crate::gpio::DriveMode::OpenDrain)
+            .set_input_inverter(crate::gpio::Inverter::Disabled);
+    }
+
+    /// Read the pin level while in [`SdaPin::as_sda_gpio`] mode.
+    fn is_gpio_high(&self) -> bool {
+        crate::gpio::get_level(self)
+    }
+
+    /// Drive the pin low or release it (it is open-drain, so "high" means released and
+    /// pulled up externally) while in [`SdaPin::as_sda_gpio`] mode, to manually issue
+    /// the STOP condition during bus recovery.
+    fn set_gpio_level(&self, high: bool) {
+        crate::gpio::set_level(self, high);
+    }
 }
 
 /// Driver mode.
@@ -348,7 +472,12 @@ impl_sda!(PIOFC15_SDA, F1, FLEXCOMM15);
 
 /// I2C Master DMA trait.
 #[allow(private_bounds)]
-pub trait MasterDma<T: Instance>: dma::Instance {}
+pub trait MasterDma<T: Instance>: dma::Instance {
+    /// `true` for the [`dma::NoDma`] placeholder, `false` for a real DMA channel.
+    /// Lets the master driver pick between the FIFO-byte and DMA-burst transfer paths
+    /// for a given channel type without needing specialization.
+    const IS_NODMA: bool = false;
+}
 
 /// I2C Slave DMA trait.
 #[allow(private_bounds)]
@@ -389,7 +518,9 @@ impl_dma!(FLEXCOMM7, Master, DMA0_CH15);
 macro_rules! impl_nodma {
     ($fcn:ident, $mode:ident) => {
         paste! {
-            impl [<$mode Dma>]<crate::peripherals::$fcn> for crate::dma::NoDma {}
+            impl [<$mode Dma>]<crate::peripherals::$fcn> for crate::dma::NoDma {
+                const IS_NODMA: bool = true;
+            }
         }
     };
 }
@@ -0,0 +1,326 @@
+//! I2C slave driver
+
+use core::future::poll_fn;
+use core::marker::PhantomData;
+use core::sync::atomic::Ordering;
+use core::task::Poll;
+
+use embassy_time::{Duration, Instant};
+
+use super::{
+    Async, Blocking, Error, Info, Instance, Mode, Result, SclPin, SdaPin, TransferError, I2C_REMEDIATION,
+    I2C_SLAVE_MATCHED_SLOT, I2C_SLAVE_NAK_SLOT, I2C_WAKERS, REMEDIATON_SLAVE_NAK,
+};
+
+/// Number of hardware address-match slots the flexcomm I2C slave supports.
+pub const ADDRESS_SLOT_COUNT: usize = 4;
+
+/// Which address matched an incoming slave transaction.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MatchedAddress {
+    /// One of the four hardware address slots matched.
+    Slot(usize),
+    /// The general-call address (`0x00`) matched.
+    GeneralCall,
+}
+
+/// A command from the bus master, reporting which address it was addressed to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Command {
+    /// The master is writing to us.
+    Write(MatchedAddress),
+    /// The master is reading from us.
+    Read(MatchedAddress),
+}
+
+/// I2C slave driver configuration.
+///
+/// Up to [`ADDRESS_SLOT_COUNT`] addresses can be registered at once, letting one
+/// peripheral emulate several logical devices. Setting `general_call` additionally
+/// claims a free slot for the reserved `0x00` general-call address.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Config {
+    /// 7-bit addresses to match; `None` leaves a slot disabled.
+    pub addresses: [Option<u8>; ADDRESS_SLOT_COUNT],
+    /// Whether to also match the general-call address (`0x00`).
+    pub general_call: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            addresses: [None; ADDRESS_SLOT_COUNT],
+            general_call: false,
+        }
+    }
+}
+
+// General call has no dedicated match logic in the flexcomm I2C block: it is matched
+// by dedicating one of the four hardware slots to the reserved `0x00` address.
+const GENERAL_CALL_ADDRESS: u8 = 0x00;
+
+/// Arms a "NAK the matched slot on drop" guard around an in-flight slave transaction. If
+/// the enclosing future is dropped before the transaction completes (a `select` losing
+/// the race, an outer timeout, ...), the next `slvpending` interrupt NAKs whichever slot
+/// was latched in `I2C_SLAVE_MATCHED_SLOT` at drop time, instead of blindly NAKing
+/// whatever happens to be pending next. [`Self::disarm`] on the success path.
+struct NakOnCancel {
+    info: Info,
+    armed: bool,
+}
+
+impl NakOnCancel {
+    fn new(info: Info) -> Self {
+        Self { info, armed: true }
+    }
+
+    fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl Drop for NakOnCancel {
+    fn drop(&mut self) {
+        if self.armed {
+            let slot = I2C_SLAVE_MATCHED_SLOT[self.info.index].load(Ordering::Acquire);
+            I2C_SLAVE_NAK_SLOT[self.info.index].store(slot, Ordering::Release);
+            I2C_REMEDIATION[self.info.index].fetch_or(REMEDIATON_SLAVE_NAK, Ordering::AcqRel);
+        }
+    }
+}
+
+/// I2C slave driver.
+pub struct I2c<'d, T: Instance, M: Mode> {
+    info: Info,
+    timeout: Duration,
+    general_call_slot: Option<usize>,
+    _instance: PhantomData<T>,
+    _mode: PhantomData<M>,
+    _lifetime: PhantomData<&'d ()>,
+}
+
+impl<'d, T: Instance> I2c<'d, T, Blocking> {
+    /// Create a new blocking I2C slave driver.
+    pub fn new_blocking<S, D>(_peripheral: T, scl: S, sda: D, config: Config) -> Result<Self>
+    where
+        S: SclPin<T>,
+        D: SdaPin<T>,
+    {
+        scl.as_scl();
+        sda.as_sda();
+
+        Self::new_inner(config)
+    }
+}
+
+impl<'d, T: Instance> I2c<'d, T, Async> {
+    /// Create a new async I2C slave driver.
+    pub fn new_async<S, D>(
+        _peripheral: T,
+        scl: S,
+        sda: D,
+        _irq: impl crate::interrupt::typelevel::Binding<T::Interrupt, super::InterruptHandler<T>>,
+        config: Config,
+    ) -> Result<Self>
+    where
+        S: SclPin<T>,
+        D: SdaPin<T>,
+    {
+        scl.as_scl();
+        sda.as_sda();
+
+        Self::new_inner(config)
+    }
+}
+
+impl<'d, T: Instance, M: Mode> I2c<'d, T, M> {
+    fn new_inner(config: Config) -> Result<Self> {
+        let info = T::info();
+        let mut addresses = config.addresses;
+
+        // `SLVADR` only has room for a 7-bit address, and two slots matching the same
+        // address would make it ambiguous which `MatchedAddress` a transaction reports.
+        for (slot, address) in addresses.iter().enumerate() {
+            let Some(address) = address else { continue };
+            if *address > 0x7F {
+                return Err(Error::AddressOutOfRange(*address as u16));
+            }
+            if addresses[..slot].iter().flatten().any(|other| other == address) {
+                return Err(Error::UnsupportedConfiguration);
+            }
+        }
+
+        let general_call_slot = if config.general_call {
+            let free = addresses
+                .iter()
+                .position(|slot| slot.is_none())
+                .ok_or(Error::UnsupportedConfiguration)?;
+            // The dedup pass above only ran over the caller-supplied addresses, before
+            // this slot existed; check it against those too so an explicit
+            // `addresses[n] = Some(0x00)` alongside `general_call = true` doesn't
+            // silently program two `SLVADR` slots to the same address.
+            if addresses.iter().flatten().any(|&other| other == GENERAL_CALL_ADDRESS) {
+                return Err(Error::UnsupportedConfiguration);
+            }
+            addresses[free] = Some(GENERAL_CALL_ADDRESS);
+            Some(free)
+        } else {
+            None
+        };
+
+        let i2c = info.regs;
+        for (slot, address) in addresses.iter().enumerate() {
+            // SAFETY: single-writer access to this instance's address registers, as
+            // guaranteed by construction holding the only `Info` for this instance.
+            unsafe {
+                match address {
+                    Some(address) => i2c.slvadr(slot).write(|w| w.slvadr().bits(*address).sadisable().clear_bit()),
+                    None => i2c.slvadr(slot).write(|w| w.sadisable().set_bit()),
+                }
+            }
+        }
+        i2c.cfg().write(|w| w.slven().set_bit());
+
+        Ok(Self {
+            info,
+            timeout: Duration::from_millis(10),
+            general_call_slot,
+            _instance: PhantomData,
+            _mode: PhantomData,
+            _lifetime: PhantomData,
+        })
+    }
+
+    /// Resolve the hardware `SLVIDX` of the pending transaction into the
+    /// [`MatchedAddress`] the user should see, mapping the general-call slot (if any)
+    /// back to [`MatchedAddress::GeneralCall`]. The slot is also recorded in
+    /// `I2C_SLAVE_MATCHED_SLOT`, as the interrupt handler does, so that it is right in
+    /// blocking mode and when `slvpending` was already set before the handler ran.
+    fn matched_address(&self) -> MatchedAddress {
+        let slot = self.info.regs.stat().read().slvidx().bits();
+        I2C_SLAVE_MATCHED_SLOT[self.info.index].store(slot, Ordering::Release);
+        let slot = slot as usize;
+        if self.general_call_slot == Some(slot) {
+            MatchedAddress::GeneralCall
+        } else {
+            MatchedAddress::Slot(slot)
+        }
+    }
+
+    fn command_for_state(&self) -> Result<Command> {
+        let matched = self.matched_address();
+        match self.info.regs.stat().read().slvstate().bits() {
+            1 => Ok(Command::Write(matched)),
+            2 => Ok(Command::Read(matched)),
+            _ => Err(Error::Transfer(TransferError::OtherBusError)),
+        }
+    }
+
+    fn blocking_wait_pending(&self, deadline: Instant) -> Result<()> {
+        loop {
+            if self.info.regs.intstat().read().slvpending().bit_is_set() {
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::Transfer(TransferError::Timeout));
+            }
+        }
+    }
+
+    async fn wait_pending(&self) -> Result<()> {
+        let index = self.info.index;
+        let i2c = self.info.regs;
+        let guard = NakOnCancel::new(self.info);
+        let fut = poll_fn(|cx| {
+            I2C_WAKERS[index].register(cx.waker());
+            if i2c.intstat().read().slvpending().bit_is_set() {
+                Poll::Ready(())
+            } else {
+                i2c.intenset().write(|w| w.slvpendingen().set_bit());
+                Poll::Pending
+            }
+        });
+
+        match embassy_time::with_timeout(self.timeout, fut).await {
+            Ok(()) => {
+                guard.disarm();
+                Ok(())
+            }
+            Err(_) => Err(Error::Transfer(TransferError::Timeout)),
+        }
+    }
+
+    /// Wait for the bus master to address one of our slots, reporting which
+    /// [`MatchedAddress`] it used and whether it wants to read or write.
+    pub fn blocking_listen(&mut self) -> Result<Command> {
+        let deadline = Instant::now() + self.timeout;
+        self.blocking_wait_pending(deadline)?;
+        let command = self.command_for_state()?;
+        self.info.regs.slvctl().write(|w| w.slvcontinue().set_bit());
+        Ok(command)
+    }
+
+    /// Receive `read.len()` bytes written by the master following a
+    /// [`Command::Write`], blocking on each byte.
+    pub fn blocking_receive(&mut self, read: &mut [u8]) -> Result<()> {
+        let deadline = Instant::now() + self.timeout;
+        for byte in read.iter_mut() {
+            self.blocking_wait_pending(deadline)?;
+            *byte = self.info.regs.slvdat().read().data().bits();
+            self.info.regs.slvctl().write(|w| w.slvcontinue().set_bit());
+        }
+        Ok(())
+    }
+
+    /// Send `write` to the master following a [`Command::Read`], blocking on each byte.
+    pub fn blocking_respond(&mut self, write: &[u8]) -> Result<()> {
+        let deadline = Instant::now() + self.timeout;
+        for &byte in write {
+            self.blocking_wait_pending(deadline)?;
+            // SAFETY: single-writer access to this instance's data register.
+            unsafe {
+                self.info.regs.slvdat().write(|w| w.data().bits(byte));
+            }
+            self.info.regs.slvctl().write(|w| w.slvcontinue().set_bit());
+        }
+        Ok(())
+    }
+}
+
+impl<'d, T: Instance> I2c<'d, T, Async> {
+    /// Wait for the bus master to address one of our slots, reporting which
+    /// [`MatchedAddress`] it used and whether it wants to read or write.
+    pub async fn listen(&mut self) -> Result<Command> {
+        self.wait_pending().await?;
+        let command = self.command_for_state()?;
+        self.info.regs.slvctl().write(|w| w.slvcontinue().set_bit());
+        Ok(command)
+    }
+
+    /// Receive `read.len()` bytes written by the master following a
+    /// [`Command::Write`].
+    pub async fn receive(&mut self, read: &mut [u8]) -> Result<()> {
+        for byte in read.iter_mut() {
+            self.wait_pending().await?;
+            *byte = self.info.regs.slvdat().read().data().bits();
+            self.info.regs.slvctl().write(|w| w.slvcontinue().set_bit());
+        }
+        Ok(())
+    }
+
+    /// Send `write` to the master following a [`Command::Read`].
+    pub async fn respond(&mut self, write: &[u8]) -> Result<()> {
+        for &byte in write {
+            self.wait_pending().await?;
+            // SAFETY: see `blocking_respond`.
+            unsafe {
+                self.info.regs.slvdat().write(|w| w.data().bits(byte));
+            }
+            self.info.regs.slvctl().write(|w| w.slvcontinue().set_bit());
+        }
+        Ok(())
+    }
+}